@@ -0,0 +1,227 @@
+//! Structured compile diagnostics and a compile-vs-reference diff mode,
+//! inspired by `fontc_crater`'s compile/diff workflow, so the editor has a
+//! regression-checking tool when babelfont edits change output
+//! unexpectedly.
+
+use read_fonts::{
+    tables::{cmap::Cmap, head::Head, hhea::Hhea, name::Name},
+    FontRef, TableProvider,
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct CompileReport {
+    stages: Vec<StageStatus>,
+    warnings: Vec<String>,
+    tables: Vec<TableInfo>,
+}
+
+#[derive(Serialize)]
+struct StageStatus {
+    name: String,
+    ok: bool,
+    detail: Option<String>,
+}
+
+#[derive(Serialize)]
+struct TableInfo {
+    tag: String,
+    size: usize,
+}
+
+/// Run the same pipeline as [`crate::compile_babelfont`] but collect
+/// per-stage status and warnings instead of bailing on the first error, and
+/// report the resulting table list/sizes.
+pub fn compile_report(babelfont_json: &str) -> CompileReport {
+    let mut stages = Vec::new();
+    let mut warnings = Vec::new();
+
+    let font: Option<babelfont::Font> = match serde_json::from_str(babelfont_json) {
+        Ok(font) => {
+            stages.push(StageStatus { name: "parse".to_string(), ok: true, detail: None });
+            Some(font)
+        }
+        Err(e) => {
+            stages.push(StageStatus {
+                name: "parse".to_string(),
+                ok: false,
+                detail: Some(e.to_string()),
+            });
+            None
+        }
+    };
+
+    let source = font.and_then(|font| {
+        match babelfont::convertors::fontir::BabelfontIrSource::new_from_memory(font) {
+            Ok(source) => {
+                stages.push(StageStatus { name: "ir_source".to_string(), ok: true, detail: None });
+                Some(source)
+            }
+            Err(e) => {
+                stages.push(StageStatus {
+                    name: "ir_source".to_string(),
+                    ok: false,
+                    detail: Some(e.to_string()),
+                });
+                None
+            }
+        }
+    });
+
+    let compiled = source.and_then(|source| {
+        let build_dir = std::path::Path::new("/tmp/fontc_build");
+        let flags = fontir::orchestration::Flags::default();
+        match fontc::generate_font(Box::new(source), build_dir, None, flags, false) {
+            Ok(bytes) => {
+                stages.push(StageStatus { name: "compile".to_string(), ok: true, detail: None });
+                Some(bytes)
+            }
+            Err(e) => {
+                stages.push(StageStatus {
+                    name: "compile".to_string(),
+                    ok: false,
+                    detail: Some(format!("{e:?}")),
+                });
+                None
+            }
+        }
+    });
+
+    let tables = compiled
+        .as_deref()
+        .and_then(|sfnt| FontRef::new(sfnt).ok())
+        .map(|font| {
+            font.table_directory
+                .table_records()
+                .iter()
+                .map(|r| TableInfo {
+                    tag: r.tag.get().to_string(),
+                    size: r.length() as usize,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if compiled.is_some() && tables.is_empty() {
+        warnings.push("Compiled font has no tables".to_string());
+    }
+
+    CompileReport { stages, warnings, tables }
+}
+
+#[derive(Serialize, Default)]
+pub struct FontDiff {
+    tables_only_in_a: Vec<String>,
+    tables_only_in_b: Vec<String>,
+    tables_differing: Vec<String>,
+    summary: Vec<String>,
+}
+
+/// Compare two compiled fonts table-by-table and summarize the differences:
+/// which tables are present in only one, which differ byte-for-byte, and a
+/// short human-readable note for cmap coverage, glyph count, `name` table,
+/// and `head`/`hhea` metric diffs.
+pub fn diff_fonts(a: &[u8], b: &[u8]) -> Result<FontDiff, String> {
+    let font_a = FontRef::new(a).map_err(|e| format!("Failed to parse font A: {e}"))?;
+    let font_b = FontRef::new(b).map_err(|e| format!("Failed to parse font B: {e}"))?;
+
+    let tags_a: Vec<_> = font_a.table_directory.table_records().iter().map(|r| r.tag.get()).collect();
+    let tags_b: Vec<_> = font_b.table_directory.table_records().iter().map(|r| r.tag.get()).collect();
+
+    let mut diff = FontDiff::default();
+    for tag in &tags_a {
+        if !tags_b.contains(tag) {
+            diff.tables_only_in_a.push(tag.to_string());
+        }
+    }
+    for tag in &tags_b {
+        if !tags_a.contains(tag) {
+            diff.tables_only_in_b.push(tag.to_string());
+        }
+    }
+    for tag in tags_a.iter().filter(|t| tags_b.contains(t)) {
+        let data_a = font_a.table_data(*tag).map(|d| d.as_bytes().to_vec());
+        let data_b = font_b.table_data(*tag).map(|d| d.as_bytes().to_vec());
+        if data_a != data_b {
+            diff.tables_differing.push(tag.to_string());
+        }
+    }
+
+    if let (Ok(cmap_a), Ok(cmap_b)) = (font_a.cmap(), font_b.cmap()) {
+        let cov_a = cmap_coverage(&cmap_a);
+        let cov_b = cmap_coverage(&cmap_b);
+        let only_a = cov_a.difference(&cov_b).count();
+        let only_b = cov_b.difference(&cov_a).count();
+        if only_a > 0 || only_b > 0 {
+            diff.summary.push(format!(
+                "cmap coverage: {only_a} codepoint(s) only in A, {only_b} only in B"
+            ));
+        }
+    }
+
+    let glyphs_a = font_a.maxp().map(|m| m.num_glyphs()).unwrap_or(0);
+    let glyphs_b = font_b.maxp().map(|m| m.num_glyphs()).unwrap_or(0);
+    if glyphs_a != glyphs_b {
+        diff.summary.push(format!("glyph count: {glyphs_a} in A vs {glyphs_b} in B"));
+    }
+
+    if let (Ok(name_a), Ok(name_b)) = (font_a.name(), font_b.name()) {
+        let diffs = name_diff(&name_a, &name_b);
+        if !diffs.is_empty() {
+            diff.summary.push(format!("name table differs for name IDs: {diffs:?}"));
+        }
+    }
+
+    if let (Ok(head_a), Ok(head_b)) = (font_a.head(), font_b.head()) {
+        diff.summary.extend(head_diff(&head_a, &head_b));
+    }
+    if let (Ok(hhea_a), Ok(hhea_b)) = (font_a.hhea(), font_b.hhea()) {
+        diff.summary.extend(hhea_diff(&hhea_a, &hhea_b));
+    }
+
+    Ok(diff)
+}
+
+fn cmap_coverage(cmap: &Cmap) -> std::collections::BTreeSet<u32> {
+    cmap.mappings().map(|(cp, _)| cp).collect()
+}
+
+fn name_diff(a: &Name, b: &Name) -> Vec<u16> {
+    // Walk records from both fonts (not just A's), so a name ID that only
+    // exists in B still surfaces as differing, and dedupe by ID since a
+    // multi-platform record repeats the same ID.
+    let mut seen = std::collections::BTreeSet::new();
+    let mut differing = Vec::new();
+    for record in a.name_record().iter().chain(b.name_record().iter()) {
+        let id = record.name_id();
+        if !seen.insert(id.to_u16()) {
+            continue;
+        }
+        if a.string_for_id(id) != b.string_for_id(id) {
+            differing.push(id.to_u16());
+        }
+    }
+    differing
+}
+
+fn head_diff(a: &Head, b: &Head) -> Vec<String> {
+    let mut out = Vec::new();
+    if a.units_per_em() != b.units_per_em() {
+        out.push(format!("unitsPerEm: {} vs {}", a.units_per_em(), b.units_per_em()));
+    }
+    if a.x_min() != b.x_min() || a.y_min() != b.y_min() || a.x_max() != b.x_max() || a.y_max() != b.y_max() {
+        out.push("head bounding box differs".to_string());
+    }
+    out
+}
+
+fn hhea_diff(a: &Hhea, b: &Hhea) -> Vec<String> {
+    let mut out = Vec::new();
+    if a.ascender() != b.ascender() {
+        out.push(format!("hhea ascender: {:?} vs {:?}", a.ascender(), b.ascender()));
+    }
+    if a.descender() != b.descender() {
+        out.push(format!("hhea descender: {:?} vs {:?}", a.descender(), b.descender()));
+    }
+    out
+}
@@ -0,0 +1,221 @@
+//! Container formats that compiled font bytes can be packaged into.
+//!
+//! `fontc::generate_font` always hands back a raw SFNT (TTF). This module
+//! wraps those bytes into the WOFF/WOFF2 web font containers so that
+//! callers don't need a second WASM module just to get something a
+//! `@font-face` rule can load efficiently.
+
+use brotli::CompressorWriter;
+use flate2::{write::ZlibEncoder, Compression};
+use read_fonts::{FontRef, ReadError, TableProvider};
+use std::io::Write;
+
+/// Output container requested from [`crate::compile_babelfont_ex`].
+#[wasm_bindgen::prelude::wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Bare SFNT/TTF bytes, no wrapping.
+    Ttf,
+    /// WOFF 1.0, zlib-compressed per table.
+    Woff,
+    /// WOFF2, Brotli-compressed as a single transformed table stream.
+    Woff2,
+}
+
+/// Wrap `sfnt` into `format`, or return it unchanged for [`OutputFormat::Ttf`].
+pub fn package(sfnt: Vec<u8>, format: OutputFormat) -> Result<Vec<u8>, String> {
+    match format {
+        OutputFormat::Ttf => Ok(sfnt),
+        OutputFormat::Woff => to_woff(&sfnt),
+        OutputFormat::Woff2 => to_woff2(&sfnt),
+    }
+}
+
+struct Table {
+    tag: [u8; 4],
+    checksum: u32,
+    data: Vec<u8>,
+}
+
+fn read_tables(sfnt: &[u8]) -> Result<(u32, Vec<Table>), String> {
+    let font = FontRef::new(sfnt).map_err(|e: ReadError| format!("Failed to parse SFNT: {e}"))?;
+    let flavor = font.table_directory.sfnt_version();
+    let mut tables = Vec::new();
+    for record in font.table_directory.table_records() {
+        let tag = record.tag.get();
+        let data = font
+            .table_data(tag)
+            .ok_or_else(|| format!("Missing table data for {tag}"))?
+            .as_bytes()
+            .to_vec();
+        tables.push(Table {
+            tag: tag.into_bytes(),
+            checksum: record.checksum(),
+            data,
+        });
+    }
+    Ok((flavor, tables))
+}
+
+const fn pad4(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Build a WOFF 1.0 file: same table directory shape as SFNT, but each
+/// table's data is individually zlib-deflated (kept raw if that doesn't
+/// shrink it).
+fn to_woff(sfnt: &[u8]) -> Result<Vec<u8>, String> {
+    let (flavor, tables) = read_tables(sfnt)?;
+    let num_tables = tables.len() as u16;
+    let header_len = 44 + tables.len() * 20;
+
+    let mut compressed_tables = Vec::with_capacity(tables.len());
+    let mut offset = header_len;
+    let mut total_sfnt_size = 12 + tables.len() * 16;
+    for table in &tables {
+        total_sfnt_size += pad4(table.data.len());
+        let mut enc = ZlibEncoder::new(Vec::new(), Compression::best());
+        enc.write_all(&table.data).map_err(|e| e.to_string())?;
+        let compressed = enc.finish().map_err(|e| e.to_string())?;
+        let (comp_len, bytes) = if compressed.len() < table.data.len() {
+            (compressed.len(), compressed)
+        } else {
+            (table.data.len(), table.data.clone())
+        };
+        offset += pad4(comp_len);
+        compressed_tables.push((table, comp_len, bytes));
+    }
+    let total_compressed_size = offset;
+
+    let mut out = Vec::with_capacity(total_compressed_size);
+    out.extend_from_slice(b"wOFF");
+    out.extend_from_slice(&flavor.to_be_bytes());
+    out.extend_from_slice(&(total_compressed_size as u32).to_be_bytes());
+    out.extend_from_slice(&num_tables.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    out.extend_from_slice(&(total_sfnt_size as u32).to_be_bytes());
+    out.extend_from_slice(&1u16.to_be_bytes()); // majorVersion
+    out.extend_from_slice(&0u16.to_be_bytes()); // minorVersion
+    out.extend_from_slice(&0u32.to_be_bytes()); // metaOffset
+    out.extend_from_slice(&0u32.to_be_bytes()); // metaLength
+    out.extend_from_slice(&0u32.to_be_bytes()); // metaOrigLength
+    out.extend_from_slice(&0u32.to_be_bytes()); // privOffset
+    out.extend_from_slice(&0u32.to_be_bytes()); // privLength
+
+    let mut data_offset = header_len as u32;
+    for (table, comp_len, _) in &compressed_tables {
+        out.extend_from_slice(&table.tag);
+        out.extend_from_slice(&data_offset.to_be_bytes());
+        out.extend_from_slice(&(*comp_len as u32).to_be_bytes());
+        out.extend_from_slice(&(table.data.len() as u32).to_be_bytes());
+        out.extend_from_slice(&table.checksum.to_be_bytes());
+        data_offset += pad4(*comp_len) as u32;
+    }
+    for (_, comp_len, bytes) in &compressed_tables {
+        out.extend_from_slice(bytes);
+        for _ in 0..(pad4(*comp_len) - comp_len) {
+            out.push(0);
+        }
+    }
+    Ok(out)
+}
+
+/// Build a WOFF2 file. Tables are concatenated (the "null" transform, i.e.
+/// glyf/loca are stored as-is rather than re-encoded) and the whole stream
+/// is Brotli-compressed once, per the WOFF2 spec's transformed-table layout.
+fn to_woff2(sfnt: &[u8]) -> Result<Vec<u8>, String> {
+    let (flavor, tables) = read_tables(sfnt)?;
+    let num_tables = tables.len() as u16;
+
+    let mut total_sfnt_size = 12 + tables.len() * 16;
+    let mut table_stream = Vec::new();
+    for table in &tables {
+        total_sfnt_size += pad4(table.data.len());
+        table_stream.extend_from_slice(&table.data);
+    }
+
+    let mut compressed = Vec::new();
+    {
+        let mut writer = CompressorWriter::new(&mut compressed, 4096, 11, 22);
+        writer.write_all(&table_stream).map_err(|e| e.to_string())?;
+    }
+
+    // Directory entries: flag byte (tag-index bits + transform version),
+    // tag (4 bytes), original table length (base128).
+    let mut directory = Vec::new();
+    for table in &tables {
+        directory.push(table_flag_byte(&table.tag));
+        directory.extend_from_slice(&table.tag);
+        write_base128(&mut directory, table.data.len() as u32);
+    }
+
+    let header_len = 48;
+    let total_compressed_size = compressed.len();
+    let total_length = header_len + directory.len() + total_compressed_size;
+
+    let mut out = Vec::with_capacity(total_length);
+    out.extend_from_slice(b"wOF2");
+    out.extend_from_slice(&flavor.to_be_bytes());
+    out.extend_from_slice(&(total_length as u32).to_be_bytes());
+    out.extend_from_slice(&num_tables.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    out.extend_from_slice(&(total_sfnt_size as u32).to_be_bytes());
+    out.extend_from_slice(&(total_compressed_size as u32).to_be_bytes());
+    out.extend_from_slice(&1u16.to_be_bytes()); // majorVersion
+    out.extend_from_slice(&0u16.to_be_bytes()); // minorVersion
+    out.extend_from_slice(&0u32.to_be_bytes()); // metaOffset
+    out.extend_from_slice(&0u32.to_be_bytes()); // metaLength
+    out.extend_from_slice(&0u32.to_be_bytes()); // metaOrigLength
+    out.extend_from_slice(&0u32.to_be_bytes()); // privOffset
+    out.extend_from_slice(&0u32.to_be_bytes()); // privLength
+    out.extend_from_slice(&directory);
+    out.extend_from_slice(&compressed);
+    Ok(out)
+}
+
+/// Directory flag byte for a table: low 6 bits select the tag-index form
+/// (`0x3f` = no known-tag shortcut, full tag follows), top 2 bits are the
+/// transform version. Per the WOFF2 spec, transform version must be 0 for
+/// every table except `glyf`/`loca`, which use 3 (the "null" transform).
+fn table_flag_byte(tag: &[u8; 4]) -> u8 {
+    let xform: u8 = if tag == b"glyf" || tag == b"loca" { 3 } else { 0 };
+    0x3f | (xform << 6)
+}
+
+fn write_base128(out: &mut Vec<u8>, mut value: u32) {
+    let mut bytes = [0u8; 5];
+    let mut i = 4;
+    bytes[4] = (value & 0x7f) as u8;
+    value >>= 7;
+    while value != 0 {
+        i -= 1;
+        bytes[i] = ((value & 0x7f) | 0x80) as u8;
+        value >>= 7;
+    }
+    out.extend_from_slice(&bytes[i..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyf_and_loca_get_null_transform() {
+        assert_eq!(table_flag_byte(b"glyf"), 0x3f | (3 << 6));
+        assert_eq!(table_flag_byte(b"loca"), 0x3f | (3 << 6));
+    }
+
+    #[test]
+    fn other_tables_get_transform_version_zero() {
+        for tag in [b"cmap", b"head", b"OS/2", b"hhea", b"name", b"post"] {
+            let flag = table_flag_byte(tag);
+            assert_eq!(
+                flag >> 6,
+                0,
+                "table {:?} must not carry a nonzero transform version",
+                std::str::from_utf8(tag).unwrap()
+            );
+            assert_eq!(flag & 0x3f, 0x3f);
+        }
+    }
+}
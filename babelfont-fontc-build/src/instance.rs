@@ -0,0 +1,173 @@
+//! Static-instance generation: interpolate masters/deltas at a requested
+//! axis location and bake the result into a plain, non-variable font.
+
+use std::collections::HashMap;
+
+/// Interpolate `font` at `axis_values` (design-space coordinates, keyed by
+/// axis tag) and return a new, static `babelfont::Font` with fvar/gvar/avar
+/// and the variation store dropped.
+pub fn instance(
+    font: &babelfont::Font,
+    axis_values: &HashMap<String, f32>,
+) -> Result<babelfont::Font, String> {
+    if font.axes.is_empty() {
+        return Err("Font is not variable: it has no axes".to_string());
+    }
+
+    let location: HashMap<String, f32> = font
+        .axes
+        .iter()
+        .map(|axis| {
+            let value = axis_values.get(&axis.tag).copied().unwrap_or(axis.default);
+            // Clamp to the axis's own range: a value beyond the outermost
+            // master has no neighbour to interpolate against, which would
+            // otherwise zero out every master's weight.
+            (axis.tag.clone(), value.clamp(axis.min, axis.max))
+        })
+        .collect();
+
+    let weights = master_weights(font, &location);
+    let survivor = survivor_master(&font.masters, &weights)?;
+
+    let mut instanced = font.clone();
+    instanced.axes.clear();
+
+    for glyph in instanced.glyphs.iter_mut() {
+        let source_glyph = font.glyphs.iter().find(|g| g.name == glyph.name);
+        let interpolated = source_glyph.and_then(|g| interpolate_layer(g, &font.masters, &weights));
+        if let Some(mut layer) = interpolated {
+            // Stamp the baked layer with the surviving master's id so it
+            // stays associated with `instanced.masters[0]` below, even
+            // though it was built from a blend that may not include
+            // `masters[0]` at all.
+            layer.id = survivor.id.clone();
+            glyph.layers = vec![layer];
+        }
+    }
+
+    instanced.masters = vec![interpolate_master(&font.masters, &weights, survivor)?];
+
+    Ok(instanced)
+}
+
+/// Compute each master's contribution to the requested location using a
+/// per-axis tent function over the distinct master coordinates on that
+/// axis, then taking the product across axes (a simplified version of the
+/// delta model `varLib` builds from a designspace).
+fn master_weights(font: &babelfont::Font, location: &HashMap<String, f32>) -> Vec<(usize, f32)> {
+    let mut weights = vec![1.0f32; font.masters.len()];
+
+    for axis in &font.axes {
+        let mut target = *location.get(&axis.tag).unwrap_or(&axis.default);
+        let mut coords: Vec<f32> = font
+            .masters
+            .iter()
+            .map(|m| m.location.get(&axis.tag).copied().unwrap_or(axis.default))
+            .collect();
+        coords.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        coords.dedup();
+
+        // The axis's declared min/max can still fall outside the master
+        // coordinate hull (e.g. a master pinned short of the axis extreme).
+        // Pull the target into that hull so the outermost master's tent
+        // always evaluates to a nonzero weight instead of every master
+        // going to zero, which would leave `instanced.masters` (one
+        // interpolated master) out of sync with glyph layers cloned from
+        // every source master.
+        if let (Some(&lo), Some(&hi)) = (coords.first(), coords.last()) {
+            target = target.clamp(lo, hi);
+        }
+
+        for (i, master) in font.masters.iter().enumerate() {
+            let peak = master.location.get(&axis.tag).copied().unwrap_or(axis.default);
+            weights[i] *= tent(target, peak, &coords);
+        }
+    }
+    weights.into_iter().enumerate().collect()
+}
+
+/// Piecewise-linear tent function peaking at `peak`, falling to zero at the
+/// neighbouring master coordinates on either side.
+fn tent(target: f32, peak: f32, coords: &[f32]) -> f32 {
+    if target == peak {
+        return 1.0;
+    }
+    let lower = coords.iter().filter(|&&c| c < peak).next_back().copied();
+    let upper = coords.iter().find(|&&c| c > peak).copied();
+
+    if target < peak {
+        match lower {
+            Some(lower) if target >= lower => (target - lower) / (peak - lower),
+            _ => 0.0,
+        }
+    } else {
+        match upper {
+            Some(upper) if target <= upper => (upper - target) / (upper - peak),
+            _ => 0.0,
+        }
+    }
+}
+
+/// Blend a glyph's per-master outlines at the requested location, resolving
+/// each contributing master's layer by master *id* (`layer_for_master`, as
+/// `metrics.rs` does) rather than by its position in `weights` — babelfont
+/// layers aren't guaranteed to sit at the same index as their master, and a
+/// glyph may carry extra non-master layers (background/brace/bracket).
+fn interpolate_layer(
+    glyph: &babelfont::Glyph,
+    masters: &[babelfont::Master],
+    weights: &[(usize, f32)],
+) -> Option<babelfont::Layer> {
+    let mut result: Option<babelfont::Layer> = None;
+    for &(master_index, weight) in weights {
+        if weight == 0.0 {
+            continue;
+        }
+        let Some(master) = masters.get(master_index) else {
+            continue;
+        };
+        let Some(layer) = glyph.layer_for_master(&master.id) else {
+            continue;
+        };
+        result = Some(match result {
+            None => layer.scaled(weight),
+            Some(acc) => acc.add_scaled(layer, weight),
+        });
+    }
+    result
+}
+
+/// The nonzero-weight master with the largest contribution to the
+/// requested location. Its `id` is what the single baked master (and every
+/// baked glyph layer) gets stamped with, so identity is preserved even
+/// though instancing collapses every source master down to one.
+fn survivor_master<'a>(
+    masters: &'a [babelfont::Master],
+    weights: &[(usize, f32)],
+) -> Result<&'a babelfont::Master, String> {
+    weights
+        .iter()
+        .filter(|&&(_, weight)| weight != 0.0)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .and_then(|&(master_index, _)| masters.get(master_index))
+        .ok_or_else(|| "No master contributes a nonzero weight at this location".to_string())
+}
+
+fn interpolate_master(
+    masters: &[babelfont::Master],
+    weights: &[(usize, f32)],
+    survivor: &babelfont::Master,
+) -> Result<babelfont::Master, String> {
+    let mut result = survivor.clone();
+    for metric_name in result.metrics.keys().cloned().collect::<Vec<_>>() {
+        let mut value = 0.0;
+        for &(master_index, weight) in weights {
+            if let Some(metric) = masters.get(master_index).and_then(|m| m.metrics.get(&metric_name)) {
+                value += metric * weight;
+            }
+        }
+        result.metrics.insert(metric_name, value);
+    }
+    result.location.clear();
+    Ok(result)
+}
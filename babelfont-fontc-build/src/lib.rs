@@ -1,37 +1,38 @@
 use wasm_bindgen::prelude::*;
 
+mod diagnostics;
+mod format;
+mod instance;
+mod manifest;
+mod metrics;
+mod render;
+mod subset;
+
+pub use format::OutputFormat;
+
 // Set up panic hook for better error messages
 #[wasm_bindgen(start)]
 pub fn init() {
     console_error_panic_hook::set_once();
 }
 
-/// Compile a font from babelfont JSON directly to TTF
-/// 
-/// This is the main entry point that takes a .babelfont JSON string
-/// and produces compiled TTF bytes.
-/// 
-/// # Arguments
-/// * `babelfont_json` - JSON string in .babelfont format
-/// 
-/// # Returns
-/// * `Vec<u8>` - Compiled TTF font bytes
-#[wasm_bindgen]
-pub fn compile_babelfont(babelfont_json: &str) -> Result<Vec<u8>, JsValue> {
+/// Compile a babelfont JSON string to an SFNT, without any container
+/// packaging. Shared by [`compile_babelfont`] and [`compile_babelfont_ex`].
+fn compile_to_sfnt(babelfont_json: &str) -> Result<Vec<u8>, JsValue> {
     // Step 1: Deserialize JSON → babelfont::Font
     let font: babelfont::Font = serde_json::from_str(babelfont_json)
         .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
-    
+
     // Step 2: Create BabelfontIrSource from the Font
     let source = babelfont::convertors::fontir::BabelfontIrSource::new_from_memory(font)
         .map_err(|e| JsValue::from_str(&format!("Failed to create IR source: {}", e)))?;
-    
+
     // Step 3: Use fontc to compile
     // Create a temporary directory for fontc's intermediate files
     // Note: In WASM, this doesn't actually write to disk
     let build_dir = std::path::Path::new("/tmp/fontc_build");
     let flags = fontir::orchestration::Flags::default();
-    
+
     let compiled_font = fontc::generate_font(
         Box::new(source),
         build_dir,
@@ -39,10 +40,267 @@ pub fn compile_babelfont(babelfont_json: &str) -> Result<Vec<u8>, JsValue> {
         flags,
         false,
     ).map_err(|e| JsValue::from_str(&format!("Compilation failed: {:?}", e)))?;
-    
+
     Ok(compiled_font)
 }
 
+/// Compile a font from babelfont JSON directly to TTF
+///
+/// This is the main entry point that takes a .babelfont JSON string
+/// and produces compiled TTF bytes.
+///
+/// # Arguments
+/// * `babelfont_json` - JSON string in .babelfont format
+///
+/// # Returns
+/// * `Vec<u8>` - Compiled TTF font bytes
+#[wasm_bindgen]
+pub fn compile_babelfont(babelfont_json: &str) -> Result<Vec<u8>, JsValue> {
+    compile_babelfont_ex(babelfont_json, OutputFormat::Ttf)
+}
+
+/// Compile a font from babelfont JSON into the requested container format.
+///
+/// # Arguments
+/// * `babelfont_json` - JSON string in .babelfont format
+/// * `format` - the container to wrap the compiled font in: `Ttf` returns
+///   the bare SFNT, `Woff`/`Woff2` wrap it for direct use in `@font-face`.
+///
+/// # Returns
+/// * `Vec<u8>` - the compiled, packaged font bytes
+#[wasm_bindgen]
+pub fn compile_babelfont_ex(
+    babelfont_json: &str,
+    format: OutputFormat,
+) -> Result<Vec<u8>, JsValue> {
+    let sfnt = compile_to_sfnt(babelfont_json)?;
+    format::package(sfnt, format).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Compute the fallback-font metrics a web app needs to synthesize a
+/// zero-layout-shift local fallback `@font-face`: `ascent-override`,
+/// `descent-override`, `line-gap-override` percentages plus the raw
+/// metrics and average advance width for deriving `size-adjust` against a
+/// named system fallback.
+///
+/// Works directly on the deserialized `babelfont::Font`, so it doesn't
+/// require a full fontc compile when only metrics are wanted.
+///
+/// # Returns
+/// * `String` - JSON-encoded [`metrics::FallbackMetrics`]
+#[wasm_bindgen]
+pub fn fallback_metrics(babelfont_json: &str) -> Result<String, JsValue> {
+    let font: babelfont::Font = serde_json::from_str(babelfont_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let metrics = metrics::fallback_metrics(&font).map_err(|e| JsValue::from_str(&e))?;
+
+    serde_json::to_string(&metrics)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize metrics: {}", e)))
+}
+
+/// Compile a font from babelfont JSON after trimming it to the requested
+/// Unicode codepoints, so the caller gets a tiny per-page subset without a
+/// separate `hb-subset` pass.
+///
+/// # Arguments
+/// * `babelfont_json` - JSON string in .babelfont format
+/// * `codepoints_json` - JSON array of codepoints and/or `[start, end]`
+///   inclusive ranges
+/// * `format` - the container to wrap the compiled font in
+#[wasm_bindgen]
+pub fn compile_babelfont_subset(
+    babelfont_json: &str,
+    codepoints_json: &str,
+    format: OutputFormat,
+) -> Result<Vec<u8>, JsValue> {
+    let mut font: babelfont::Font = serde_json::from_str(babelfont_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let codepoints =
+        subset::parse_codepoints(codepoints_json).map_err(|e| JsValue::from_str(&e))?;
+    subset::subset(&mut font, &codepoints).map_err(|e| JsValue::from_str(&e))?;
+
+    let source = babelfont::convertors::fontir::BabelfontIrSource::new_from_memory(font)
+        .map_err(|e| JsValue::from_str(&format!("Failed to create IR source: {}", e)))?;
+
+    let build_dir = std::path::Path::new("/tmp/fontc_build");
+    let flags = fontir::orchestration::Flags::default();
+    let sfnt = fontc::generate_font(Box::new(source), build_dir, None, flags, false)
+        .map_err(|e| JsValue::from_str(&format!("Compilation failed: {:?}", e)))?;
+
+    format::package(sfnt, format).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Rasterize a single glyph, looked up by codepoint or glyph name, to PNG
+/// bytes sized for in-browser previews, analogous to how Pathfinder's demo
+/// server rendered reference images.
+///
+/// # Arguments
+/// * `babelfont_json` - JSON string in .babelfont format
+/// * `unicode_or_glyphname` - a single character (looked up via cmap) or a
+///   glyph name
+/// * `size_px` - the em-square size to render at, in pixels
+/// * `axis_values_json` - optional `{"wght": 650, "wdth": 87.5}` variation
+///   coordinates to preview a variable font at a specific instance
+/// * `background` / `foreground` - optional `#rrggbb(aa)` hex colors,
+///   defaulting to transparent background and opaque black foreground
+///
+/// # Returns
+/// * `Vec<u8>` - PNG-encoded raster
+#[wasm_bindgen]
+pub fn render_glyph(
+    babelfont_json: &str,
+    unicode_or_glyphname: &str,
+    size_px: f32,
+    axis_values_json: Option<String>,
+    background: Option<String>,
+    foreground: Option<String>,
+) -> Result<Vec<u8>, JsValue> {
+    let sfnt = compile_to_sfnt(babelfont_json)?;
+    let axis_values = axis_values_json
+        .as_deref()
+        .map(render::parse_axis_values)
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?
+        .unwrap_or_default();
+    let background = background
+        .as_deref()
+        .map(render::parse_color)
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?
+        .unwrap_or(render::DEFAULT_BACKGROUND);
+    let foreground = foreground
+        .as_deref()
+        .map(render::parse_color)
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?
+        .unwrap_or(render::DEFAULT_FOREGROUND);
+
+    render::render_glyph(&sfnt, unicode_or_glyphname, size_px, &axis_values, background, foreground)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Rasterize a run of text (simple cmap advance-width placement, no
+/// OpenType shaping) to PNG bytes. See [`render_glyph`] for the shared
+/// arguments.
+#[wasm_bindgen]
+pub fn render_string(
+    babelfont_json: &str,
+    text: &str,
+    size_px: f32,
+    axis_values_json: Option<String>,
+    background: Option<String>,
+    foreground: Option<String>,
+) -> Result<Vec<u8>, JsValue> {
+    let sfnt = compile_to_sfnt(babelfont_json)?;
+    let axis_values = axis_values_json
+        .as_deref()
+        .map(render::parse_axis_values)
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?
+        .unwrap_or_default();
+    let background = background
+        .as_deref()
+        .map(render::parse_color)
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?
+        .unwrap_or(render::DEFAULT_BACKGROUND);
+    let foreground = foreground
+        .as_deref()
+        .map(render::parse_color)
+        .transpose()
+        .map_err(|e| JsValue::from_str(&e))?
+        .unwrap_or(render::DEFAULT_FOREGROUND);
+
+    render::render_string(&sfnt, text, size_px, &axis_values, background, foreground)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Compile a static instance of a variable babelfont design pinned at the
+/// given axis coordinates, e.g. `{"wght": 650, "wdth": 87.5}`. Interpolates
+/// masters/deltas at that location and drops fvar/gvar/avar and the
+/// variation store, so the web UI can export a single named weight without
+/// the user hand-pinning every axis.
+///
+/// # Arguments
+/// * `babelfont_json` - JSON string in .babelfont format
+/// * `axis_values_json` - JSON object of axis tag to design-space value
+/// * `format` - the container to wrap the compiled font in
+#[wasm_bindgen]
+pub fn instance_babelfont(
+    babelfont_json: &str,
+    axis_values_json: &str,
+    format: OutputFormat,
+) -> Result<Vec<u8>, JsValue> {
+    let font: babelfont::Font = serde_json::from_str(babelfont_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let axis_values: std::collections::HashMap<String, f32> =
+        serde_json::from_str(axis_values_json)
+            .map_err(|e| JsValue::from_str(&format!("Invalid axis values JSON: {}", e)))?;
+
+    let instanced = instance::instance(&font, &axis_values).map_err(|e| JsValue::from_str(&e))?;
+
+    let source = babelfont::convertors::fontir::BabelfontIrSource::new_from_memory(instanced)
+        .map_err(|e| JsValue::from_str(&format!("Failed to create IR source: {}", e)))?;
+
+    let build_dir = std::path::Path::new("/tmp/fontc_build");
+    let flags = fontir::orchestration::Flags::default();
+    let sfnt = fontc::generate_font(Box::new(source), build_dir, None, flags, false)
+        .map_err(|e| JsValue::from_str(&format!("Compilation failed: {:?}", e)))?;
+
+    format::package(sfnt, format).map_err(|e| JsValue::from_str(&e))
+}
+
+/// Run the same compile pipeline as [`compile_babelfont`], but instead of
+/// bailing on the first error, collect per-stage status, any warnings, and
+/// the resulting table list/sizes into a single JSON report.
+///
+/// # Returns
+/// * `String` - JSON-encoded [`diagnostics::CompileReport`]
+#[wasm_bindgen]
+pub fn compile_babelfont_report(babelfont_json: &str) -> Result<String, JsValue> {
+    let report = diagnostics::compile_report(babelfont_json);
+    serde_json::to_string(&report)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize report: {}", e)))
+}
+
+/// Diff two compiled fonts at the table level: which tables exist in only
+/// one, which differ byte-for-byte, and a short summary of differing
+/// records (cmap coverage, glyph count, `name` table, `head`/`hhea`
+/// metrics). Useful as a regression check when a babelfont edit changes
+/// compiled output unexpectedly.
+///
+/// # Returns
+/// * `String` - JSON-encoded [`diagnostics::FontDiff`]
+#[wasm_bindgen]
+pub fn diff_fonts(ttf_a: &[u8], ttf_b: &[u8]) -> Result<String, JsValue> {
+    let diff = diagnostics::diff_fonts(ttf_a, ttf_b).map_err(|e| JsValue::from_str(&e))?;
+    serde_json::to_string(&diff)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize diff: {}", e)))
+}
+
+/// Extract a font catalog/manifest from babelfont JSON: family name,
+/// subfamily/style, PostScript name, full name, weight/width/italic
+/// values, variable-font axes with min/default/max, and the covered
+/// Unicode codepoint ranges. Lets the editor build family/style pickers
+/// and coverage badges without separately parsing the binary, and gives a
+/// stable interchange format for downstream packaging.
+///
+/// # Returns
+/// * `String` - JSON-encoded [`manifest::FontManifest`]
+#[wasm_bindgen]
+pub fn font_manifest(babelfont_json: &str) -> Result<String, JsValue> {
+    let font: babelfont::Font = serde_json::from_str(babelfont_json)
+        .map_err(|e| JsValue::from_str(&format!("JSON parse error: {}", e)))?;
+
+    let manifest = manifest::font_manifest(&font).map_err(|e| JsValue::from_str(&e))?;
+
+    serde_json::to_string(&manifest)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize manifest: {}", e)))
+}
+
 /// Legacy function for compatibility
 #[wasm_bindgen]
 pub fn compile_glyphs(_glyphs_json: &str) -> Result<Vec<u8>, JsValue> {
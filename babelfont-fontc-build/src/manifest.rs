@@ -0,0 +1,92 @@
+//! Font catalog/manifest extraction, following the model of Fuchsia's
+//! manifest generator: a stable, parseable interchange format the editor
+//! can use to build family/style pickers and coverage badges without
+//! separately parsing the binary.
+
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct FontManifest {
+    family_name: String,
+    subfamily_name: String,
+    postscript_name: String,
+    full_name: String,
+    weight: f64,
+    width: f64,
+    italic: bool,
+    axes: Vec<AxisInfo>,
+    /// Compactly range-encoded Unicode coverage: `[start, end]` inclusive.
+    unicode_ranges: Vec<[u32; 2]>,
+}
+
+#[derive(Serialize)]
+struct AxisInfo {
+    tag: String,
+    name: String,
+    min: f64,
+    default: f64,
+    max: f64,
+}
+
+/// Build a [`FontManifest`] directly from the deserialized `babelfont::Font`.
+pub fn font_manifest(font: &babelfont::Font) -> Result<FontManifest, String> {
+    let names = &font.names;
+    let master = font
+        .default_master()
+        .ok_or_else(|| "Font has no default master".to_string())?;
+
+    let axes = font
+        .axes
+        .iter()
+        .map(|axis| AxisInfo {
+            tag: axis.tag.clone(),
+            name: axis.name.clone(),
+            min: axis.min,
+            default: axis.default,
+            max: axis.max,
+        })
+        .collect();
+
+    let codepoints: std::collections::BTreeSet<u32> = font
+        .glyphs
+        .iter()
+        .flat_map(|g| g.codepoints.iter().copied())
+        .collect();
+
+    Ok(FontManifest {
+        family_name: names.family_name.clone().unwrap_or_default(),
+        subfamily_name: master.name.clone().unwrap_or_default(),
+        postscript_name: names.postscript_name.clone().unwrap_or_default(),
+        full_name: names.full_name.clone().unwrap_or_default(),
+        // Weight/width are axis coordinates, not vertical metrics: they
+        // live in the master's `location` (keyed by axis tag), same as
+        // `instance.rs`/`master_weights` read them.
+        weight: master.location.get("wght").copied().unwrap_or(400.0) as f64,
+        width: master.location.get("wdth").copied().unwrap_or(100.0) as f64,
+        italic: master.italic_angle().unwrap_or(0.0) != 0.0,
+        axes,
+        unicode_ranges: range_encode(&codepoints),
+    })
+}
+
+/// Compactly range-encode a sorted set of codepoints into inclusive
+/// `[start, end]` pairs.
+fn range_encode(codepoints: &std::collections::BTreeSet<u32>) -> Vec<[u32; 2]> {
+    let mut ranges = Vec::new();
+    let mut iter = codepoints.iter().copied();
+    let Some(mut start) = iter.next() else {
+        return ranges;
+    };
+    let mut end = start;
+    for cp in iter {
+        if cp == end + 1 {
+            end = cp;
+        } else {
+            ranges.push([start, end]);
+            start = cp;
+            end = cp;
+        }
+    }
+    ranges.push([start, end]);
+    ranges
+}
@@ -0,0 +1,86 @@
+//! Fallback-font metrics for zero-layout-shift local `@font-face` rules,
+//! mirroring what `next/font/local` derives via allsorts.
+
+use serde::Serialize;
+
+/// Metrics needed to synthesize a `size-adjust`/`ascent-override` fallback
+/// `@font-face` that lines up with this font without loading it.
+#[derive(Serialize)]
+pub struct FallbackMetrics {
+    units_per_em: f64,
+    ascent: f64,
+    descent: f64,
+    line_gap: f64,
+    cap_height: f64,
+    x_height: f64,
+    average_advance_width: f64,
+    /// `ascent / unitsPerEm * 100`
+    ascent_override: f64,
+    /// `abs(descent) / unitsPerEm * 100`
+    descent_override: f64,
+    /// `lineGap / unitsPerEm * 100`
+    line_gap_override: f64,
+}
+
+/// Derive [`FallbackMetrics`] directly from a `babelfont::Font`, without
+/// running it through fontc — the master/default values already carry
+/// everything CSS needs.
+pub fn fallback_metrics(font: &babelfont::Font) -> Result<FallbackMetrics, String> {
+    let upm = font.upm as f64;
+    if upm == 0.0 {
+        return Err("Font has unitsPerEm of 0".to_string());
+    }
+
+    let master = font
+        .default_master()
+        .ok_or_else(|| "Font has no default master".to_string())?;
+
+    let ascent = master.metrics.get("ascender").copied().unwrap_or(0.0);
+    let descent = master.metrics.get("descender").copied().unwrap_or(0.0);
+    let line_gap = master.metrics.get("lineGap").copied().unwrap_or(0.0);
+    let cap_height = master.metrics.get("capHeight").copied().unwrap_or(0.0);
+    let x_height = master.metrics.get("xHeight").copied().unwrap_or(0.0);
+
+    let average_advance_width = average_ascii_advance(font, master);
+
+    Ok(FallbackMetrics {
+        units_per_em: upm,
+        ascent,
+        descent,
+        line_gap,
+        cap_height,
+        x_height,
+        average_advance_width,
+        ascent_override: ascent / upm * 100.0,
+        descent_override: descent.abs() / upm * 100.0,
+        line_gap_override: line_gap / upm * 100.0,
+    })
+}
+
+/// Average advance width across the ASCII-printable range (0x20..=0x7e)
+/// that the font actually covers, falling back to the full cmap if none
+/// of those codepoints are present.
+fn average_ascii_advance(font: &babelfont::Font, master: &babelfont::Master) -> f64 {
+    let codepoints: Vec<u32> = (0x20u32..=0x7e).collect();
+    let widths = advance_widths_for(font, master, &codepoints);
+    if !widths.is_empty() {
+        return widths.iter().sum::<f64>() / widths.len() as f64;
+    }
+
+    let all_codepoints: Vec<u32> = font.glyphs.iter().flat_map(|g| g.codepoints.iter().copied()).collect();
+    let widths = advance_widths_for(font, master, &all_codepoints);
+    if widths.is_empty() {
+        0.0
+    } else {
+        widths.iter().sum::<f64>() / widths.len() as f64
+    }
+}
+
+fn advance_widths_for(font: &babelfont::Font, master: &babelfont::Master, codepoints: &[u32]) -> Vec<f64> {
+    codepoints
+        .iter()
+        .filter_map(|cp| font.glyph_for_codepoint(*cp))
+        .filter_map(|glyph| glyph.layer_for_master(&master.id))
+        .map(|layer| layer.width)
+        .collect()
+}
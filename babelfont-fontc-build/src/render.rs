@@ -0,0 +1,585 @@
+//! In-browser glyph/string preview rasterization, so the editor can show a
+//! live raster without round-tripping through a `<canvas>` + opentype.js.
+
+use skrifa::{
+    instance::{Location, Size},
+    outline::{DrawSettings, OutlinePen},
+    raw::TableProvider,
+    FontRef as SkrifaFontRef, GlyphId, MetadataProvider,
+};
+use std::collections::HashMap;
+
+/// RGBA color, `0xRRGGBBAA`.
+pub type Color = [u8; 4];
+
+pub const DEFAULT_BACKGROUND: Color = [0, 0, 0, 0];
+pub const DEFAULT_FOREGROUND: Color = [0, 0, 0, 255];
+
+/// Supersampling factor for antialiasing: each output pixel is resolved
+/// from a `SUPERSAMPLE x SUPERSAMPLE` grid of coverage samples.
+const SUPERSAMPLE: usize = 4;
+
+/// Render a single glyph (looked up by codepoint or glyph name) to an RGBA
+/// raster, then PNG-encode it.
+pub fn render_glyph(
+    sfnt: &[u8],
+    unicode_or_glyphname: &str,
+    size_px: f32,
+    axis_values: &HashMap<String, f32>,
+    background: Color,
+    foreground: Color,
+) -> Result<Vec<u8>, String> {
+    let font = SkrifaFontRef::new(sfnt).map_err(|e| format!("Failed to parse font: {e}"))?;
+    let gid = resolve_glyph(&font, unicode_or_glyphname)?;
+    let (width, height, outline, _descent_px, _x_origin_px) =
+        rasterize_glyph(&font, gid, size_px, axis_values)?;
+    encode_png(width, height, &paint(width, height, &outline, background, foreground))
+}
+
+/// Render a run of glyphs (shaped left-to-right using simple cmap
+/// advance-width placement, no OpenType shaping) to a single raster.
+pub fn render_string(
+    sfnt: &[u8],
+    text: &str,
+    size_px: f32,
+    axis_values: &HashMap<String, f32>,
+    background: Color,
+    foreground: Color,
+) -> Result<Vec<u8>, String> {
+    let font = SkrifaFontRef::new(sfnt).map_err(|e| format!("Failed to parse font: {e}"))?;
+    let units_per_em = font.head().map(|h| h.units_per_em()).unwrap_or(1000) as f32;
+    let scale = size_px / units_per_em;
+    let location = location_for(&font, axis_values);
+    let hhea = font.hhea().ok();
+    let ascent_px = (hhea.as_ref().map(|h| h.ascender().to_i16()).unwrap_or(0) as f32 * scale)
+        .max(0.0)
+        .ceil() as usize;
+    let descent_px = ((-(hhea.as_ref().map(|h| h.descender().to_i16()).unwrap_or(0) as f32)) * scale)
+        .max(0.0)
+        .ceil() as usize;
+
+    let mut pen_x = 0.0f32;
+    let mut coverages = Vec::new();
+    for ch in text.chars() {
+        let gid = font
+            .charmap()
+            .map(ch)
+            .ok_or_else(|| format!("No glyph for character {ch:?}"))?;
+        let (w, h, outline, glyph_descent_px, x_origin_px) =
+            rasterize_with_location(&font, gid, scale, &location)?;
+        let glyph_ascent_px = h.saturating_sub(glyph_descent_px);
+        let x0 = pen_x.round() as isize + x_origin_px;
+        coverages.push((x0, w, h, glyph_ascent_px, outline));
+        let advance = font
+            .glyph_metrics(Size::new(size_px), &location)
+            .advance_width(gid)
+            .unwrap_or(0.0);
+        pen_x += advance;
+    }
+    let width = pen_x.ceil() as usize;
+    let height = ascent_px + descent_px;
+
+    let mut image = vec![background; width * height];
+    for (x0, w, h, glyph_ascent_px, outline) in coverages {
+        // Shift this glyph so its own baseline lands on the shared
+        // baseline at row `ascent_px`, instead of top-aligning bitmaps
+        // of differing heights (cap-height vs. descending glyphs).
+        let y_offset = ascent_px as isize - glyph_ascent_px as isize;
+        for y in 0..h {
+            let py = y as isize + y_offset;
+            if py < 0 || py as usize >= height {
+                continue;
+            }
+            for x in 0..w {
+                let px = x0 + x as isize;
+                if px < 0 || px as usize >= width {
+                    continue;
+                }
+                let coverage = outline[y * w + x];
+                image[py as usize * width + px as usize] = blend(background, foreground, coverage);
+            }
+        }
+    }
+    encode_png(width, height, &image)
+}
+
+fn resolve_glyph(font: &SkrifaFontRef, unicode_or_glyphname: &str) -> Result<GlyphId, String> {
+    if let Some(ch) = single_char(unicode_or_glyphname) {
+        if let Some(gid) = font.charmap().map(ch) {
+            return Ok(gid);
+        }
+    }
+    // Not a single character resolvable via cmap: treat the whole string
+    // as a glyph name and look it up directly via `post`.
+    font.glyph_names()
+        .find(|(_, name)| name == unicode_or_glyphname)
+        .map(|(gid, _)| gid)
+        .ok_or_else(|| format!("No glyph found for {unicode_or_glyphname:?}"))
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    let first = chars.next()?;
+    chars.next().is_none().then_some(first)
+}
+
+fn location_for(font: &SkrifaFontRef, axis_values: &HashMap<String, f32>) -> Location {
+    let coords: Vec<(&str, f32)> = axis_values.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    font.axes().location(coords)
+}
+
+fn rasterize_glyph(
+    font: &SkrifaFontRef,
+    gid: GlyphId,
+    size_px: f32,
+    axis_values: &HashMap<String, f32>,
+) -> Result<(usize, usize, Vec<f32>, usize, isize), String> {
+    let units_per_em = font.head().map(|h| h.units_per_em()).unwrap_or(1000) as f32;
+    let scale = size_px / units_per_em;
+    let location = location_for(font, axis_values);
+    rasterize_with_location(font, gid, scale, &location)
+}
+
+struct SupersamplePen {
+    width: usize,
+    height: usize,
+    scale: f32,
+    x0: f32,
+    y0: f32,
+    // Pending contour state used to scan-convert on close.
+    contours: Vec<Vec<(f32, f32)>>,
+    current: Vec<(f32, f32)>,
+}
+
+impl SupersamplePen {
+    fn to_device(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.x0) * self.scale * SUPERSAMPLE as f32,
+            (self.height as f32 * SUPERSAMPLE as f32) - (y - self.y0) * self.scale * SUPERSAMPLE as f32,
+        )
+    }
+}
+
+impl OutlinePen for SupersamplePen {
+    fn move_to(&mut self, x: f32, y: f32) {
+        if !self.current.is_empty() {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+        self.current.push(self.to_device(x, y));
+    }
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.current.push(self.to_device(x, y));
+    }
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        let (x0, y0) = *self.current.last().unwrap();
+        let (cx, cy) = self.to_device(cx0, cy0);
+        let (x1, y1) = self.to_device(x, y);
+        for i in 1..=8 {
+            let t = i as f32 / 8.0;
+            let mt = 1.0 - t;
+            let px = mt * mt * x0 + 2.0 * mt * t * cx + t * t * x1;
+            let py = mt * mt * y0 + 2.0 * mt * t * cy + t * t * y1;
+            self.current.push((px, py));
+        }
+    }
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let (x0, y0) = *self.current.last().unwrap();
+        let (cx0, cy0) = self.to_device(cx0, cy0);
+        let (cx1, cy1) = self.to_device(cx1, cy1);
+        let (x1, y1) = self.to_device(x, y);
+        for i in 1..=8 {
+            let t = i as f32 / 8.0;
+            let mt = 1.0 - t;
+            let px = mt * mt * mt * x0
+                + 3.0 * mt * mt * t * cx0
+                + 3.0 * mt * t * t * cx1
+                + t * t * t * x1;
+            let py = mt * mt * mt * y0
+                + 3.0 * mt * mt * t * cy0
+                + 3.0 * mt * t * t * cy1
+                + t * t * t * y1;
+            self.current.push((px, py));
+        }
+    }
+    fn close(&mut self) {
+        if !self.current.is_empty() {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+fn rasterize_with_location(
+    font: &SkrifaFontRef,
+    gid: GlyphId,
+    scale: f32,
+    location: &Location,
+) -> Result<(usize, usize, Vec<f32>, usize, isize), String> {
+    let glyph_metrics = font.glyph_metrics(Size::unscaled(), location);
+    let bounds = font
+        .outline_glyphs()
+        .get(gid)
+        .and_then(|g| g.bounding_box(location, Size::unscaled()))
+        .unwrap_or_default();
+    let advance = glyph_metrics.advance_width(gid).unwrap_or(0.0);
+
+    let x0 = bounds.x_min.min(0.0);
+    let width = (((advance.max(bounds.x_max) - x0) * scale).ceil().max(1.0)) as usize;
+    let height = ((bounds.y_max - bounds.y_min.min(0.0)) * scale).ceil().max(1.0) as usize;
+    let y0 = bounds.y_min.min(0.0);
+
+    let mut pen = SupersamplePen {
+        width,
+        height,
+        scale,
+        x0,
+        y0,
+        contours: Vec::new(),
+        current: Vec::new(),
+    };
+
+    let outline_glyphs = font.outline_glyphs();
+    if let Some(outline) = outline_glyphs.get(gid) {
+        outline
+            .draw(DrawSettings::unhinted(Size::unscaled(), location), &mut pen)
+            .map_err(|e| format!("Failed to draw glyph outline: {e}"))?;
+    }
+    if !pen.current.is_empty() {
+        pen.contours.push(std::mem::take(&mut pen.current));
+    }
+
+    let ss_width = width * SUPERSAMPLE;
+    let ss_height = height * SUPERSAMPLE;
+    let mask = scanline_fill(&pen.contours, ss_width, ss_height);
+
+    let mut coverage = vec![0f32; width * height];
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = 0u32;
+            for sy in 0..SUPERSAMPLE {
+                for sx in 0..SUPERSAMPLE {
+                    let px = x * SUPERSAMPLE + sx;
+                    let py = y * SUPERSAMPLE + sy;
+                    if mask[py * ss_width + px] {
+                        sum += 1;
+                    }
+                }
+            }
+            coverage[y * width + x] = sum as f32 / (SUPERSAMPLE * SUPERSAMPLE) as f32;
+        }
+    }
+    // Rows of the bitmap that fall below the baseline (y < 0 in font
+    // space), so callers can align multiple glyphs to a shared baseline.
+    let descent_px = (-y0 * scale).max(0.0).ceil() as usize;
+    // Column the bitmap's left edge sits at relative to the glyph origin
+    // (pen position), in pixels: zero, or negative when the outline
+    // overshoots to the left of the origin.
+    let x_origin_px = (x0 * scale).round() as isize;
+    Ok((width, height, coverage, descent_px, x_origin_px))
+}
+
+/// Nonzero-winding scanline fill over a supersampled pixel grid.
+fn scanline_fill(contours: &[Vec<(f32, f32)>], width: usize, height: usize) -> Vec<bool> {
+    let mut mask = vec![false; width * height];
+    for y in 0..height {
+        let scan_y = y as f32 + 0.5;
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        for contour in contours {
+            if contour.len() < 2 {
+                continue;
+            }
+            for i in 0..contour.len() {
+                let (x0, y0) = contour[i];
+                let (x1, y1) = contour[(i + 1) % contour.len()];
+                if (y0 <= scan_y && y1 > scan_y) || (y1 <= scan_y && y0 > scan_y) {
+                    let t = (scan_y - y0) / (y1 - y0);
+                    let x = x0 + t * (x1 - x0);
+                    let winding = if y1 > y0 { 1 } else { -1 };
+                    crossings.push((x, winding));
+                }
+            }
+        }
+        crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mut winding = 0;
+        let mut iter = crossings.into_iter().peekable();
+        while let Some((x, w)) = iter.next() {
+            let was_inside = winding != 0;
+            winding += w;
+            let is_inside = winding != 0;
+            if !was_inside && is_inside {
+                // Entering a filled span; find where it ends.
+                let start = x;
+                let mut end = start;
+                let mut inner_winding = winding;
+                while let Some(&(next_x, _)) = iter.peek() {
+                    if inner_winding == 0 {
+                        break;
+                    }
+                    let (nx, nw) = iter.next().unwrap();
+                    inner_winding += nw;
+                    end = next_x;
+                    let _ = nx;
+                }
+                winding = inner_winding;
+                let x_start = start.max(0.0) as usize;
+                let x_end = (end.min(width as f32)) as usize;
+                for x in x_start..x_end.min(width) {
+                    mask[y * width + x] = true;
+                }
+            }
+        }
+    }
+    mask
+}
+
+fn paint(width: usize, height: usize, coverage: &[f32], background: Color, foreground: Color) -> Vec<Color> {
+    (0..width * height)
+        .map(|i| blend(background, foreground, coverage[i]))
+        .collect()
+}
+
+fn blend(background: Color, foreground: Color, coverage: f32) -> Color {
+    let mut out = [0u8; 4];
+    for c in 0..4 {
+        let bg = background[c] as f32;
+        let fg = foreground[c] as f32;
+        out[c] = (bg + (fg - bg) * coverage).round() as u8;
+    }
+    out
+}
+
+/// Parse a `#rrggbb` or `#rrggbbaa` hex color, defaulting alpha to opaque.
+pub fn parse_color(hex: &str) -> Result<Color, String> {
+    let hex = hex.trim_start_matches('#');
+    let channel = |i: usize| -> Result<u8, String> {
+        u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| format!("Invalid color {hex:?}: {e}"))
+    };
+    match hex.len() {
+        6 => Ok([channel(0)?, channel(2)?, channel(4)?, 255]),
+        8 => Ok([channel(0)?, channel(2)?, channel(4)?, channel(6)?]),
+        _ => Err(format!("Invalid color {hex:?}: expected #rrggbb or #rrggbbaa")),
+    }
+}
+
+/// Parse a `{"wght": 650, "wdth": 87.5}` variation-coordinate map.
+pub fn parse_axis_values(json: &str) -> Result<HashMap<String, f32>, String> {
+    serde_json::from_str(json).map_err(|e| format!("Invalid axis values JSON: {e}"))
+}
+
+fn encode_png(width: usize, height: usize, pixels: &[Color]) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG header: {e}"))?;
+        let flat: Vec<u8> = pixels.iter().flat_map(|p| p.iter().copied()).collect();
+        writer
+            .write_image_data(&flat)
+            .map_err(|e| format!("Failed to write PNG data: {e}"))?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hand-build a minimal 3-glyph TTF (.notdef, "A", "A.sc") with a cmap
+    /// mapping U+0041 to "A" and a post-table 2.0 name list, so
+    /// `resolve_glyph` can be exercised against both lookup paths without
+    /// pulling in a real compiled font.
+    fn build_test_font() -> Vec<u8> {
+        fn pad4(mut data: Vec<u8>) -> Vec<u8> {
+            while data.len() % 4 != 0 {
+                data.push(0);
+            }
+            data
+        }
+
+        let head = {
+            let mut t = Vec::new();
+            t.extend_from_slice(&0x00010000u32.to_be_bytes()); // version
+            t.extend_from_slice(&0u32.to_be_bytes()); // fontRevision
+            t.extend_from_slice(&0u32.to_be_bytes()); // checkSumAdjustment
+            t.extend_from_slice(&0x5F0F3CF5u32.to_be_bytes()); // magicNumber
+            t.extend_from_slice(&0u16.to_be_bytes()); // flags
+            t.extend_from_slice(&1000u16.to_be_bytes()); // unitsPerEm
+            t.extend_from_slice(&0i64.to_be_bytes()); // created
+            t.extend_from_slice(&0i64.to_be_bytes()); // modified
+            t.extend_from_slice(&0i16.to_be_bytes()); // xMin
+            t.extend_from_slice(&0i16.to_be_bytes()); // yMin
+            t.extend_from_slice(&0i16.to_be_bytes()); // xMax
+            t.extend_from_slice(&0i16.to_be_bytes()); // yMax
+            t.extend_from_slice(&0u16.to_be_bytes()); // macStyle
+            t.extend_from_slice(&0u16.to_be_bytes()); // lowestRecPPEM
+            t.extend_from_slice(&2i16.to_be_bytes()); // fontDirectionHint
+            t.extend_from_slice(&0i16.to_be_bytes()); // indexToLocFormat (short)
+            t.extend_from_slice(&0i16.to_be_bytes()); // glyphDataFormat
+            t
+        };
+
+        let hhea = {
+            let mut t = Vec::new();
+            t.extend_from_slice(&0x00010000u32.to_be_bytes());
+            t.extend_from_slice(&800i16.to_be_bytes()); // ascender
+            t.extend_from_slice(&(-200i16).to_be_bytes()); // descender
+            t.extend_from_slice(&0i16.to_be_bytes()); // lineGap
+            t.extend_from_slice(&500u16.to_be_bytes()); // advanceWidthMax
+            t.extend_from_slice(&0i16.to_be_bytes());
+            t.extend_from_slice(&0i16.to_be_bytes());
+            t.extend_from_slice(&0i16.to_be_bytes());
+            t.extend_from_slice(&1i16.to_be_bytes()); // caretSlopeRise
+            t.extend_from_slice(&0i16.to_be_bytes()); // caretSlopeRun
+            t.extend_from_slice(&0i16.to_be_bytes()); // caretOffset
+            for _ in 0..4 {
+                t.extend_from_slice(&0i16.to_be_bytes()); // reserved
+            }
+            t.extend_from_slice(&0i16.to_be_bytes()); // metricDataFormat
+            t.extend_from_slice(&3u16.to_be_bytes()); // numberOfHMetrics
+            t
+        };
+
+        let maxp = {
+            let mut t = Vec::new();
+            t.extend_from_slice(&0x00005000u32.to_be_bytes()); // version 0.5
+            t.extend_from_slice(&3u16.to_be_bytes()); // numGlyphs
+            t
+        };
+
+        let hmtx = {
+            let mut t = Vec::new();
+            for _ in 0..3 {
+                t.extend_from_slice(&500u16.to_be_bytes()); // advanceWidth
+                t.extend_from_slice(&0i16.to_be_bytes()); // lsb
+            }
+            t
+        };
+
+        // Short-format loca: all glyphs empty.
+        let loca = {
+            let mut t = Vec::new();
+            for _ in 0..4 {
+                t.extend_from_slice(&0u16.to_be_bytes());
+            }
+            t
+        };
+        let glyf: Vec<u8> = Vec::new();
+
+        // cmap format 4: one segment mapping U+0041 -> gid 1, plus the
+        // required 0xFFFF sentinel segment.
+        let cmap_subtable = {
+            let mut t = Vec::new();
+            t.extend_from_slice(&4u16.to_be_bytes()); // format
+            t.extend_from_slice(&32u16.to_be_bytes()); // length
+            t.extend_from_slice(&0u16.to_be_bytes()); // language
+            t.extend_from_slice(&4u16.to_be_bytes()); // segCountX2
+            t.extend_from_slice(&4u16.to_be_bytes()); // searchRange
+            t.extend_from_slice(&1u16.to_be_bytes()); // entrySelector
+            t.extend_from_slice(&0u16.to_be_bytes()); // rangeShift
+            t.extend_from_slice(&0x0041u16.to_be_bytes()); // endCode[0]
+            t.extend_from_slice(&0xFFFFu16.to_be_bytes()); // endCode[1]
+            t.extend_from_slice(&0u16.to_be_bytes()); // reservedPad
+            t.extend_from_slice(&0x0041u16.to_be_bytes()); // startCode[0]
+            t.extend_from_slice(&0xFFFFu16.to_be_bytes()); // startCode[1]
+            t.extend_from_slice(&(1i16 - 0x0041i16).to_be_bytes()); // idDelta[0]: gid 1
+            t.extend_from_slice(&1i16.to_be_bytes()); // idDelta[1]
+            t.extend_from_slice(&0u16.to_be_bytes()); // idRangeOffset[0]
+            t.extend_from_slice(&0u16.to_be_bytes()); // idRangeOffset[1]
+            t
+        };
+        let cmap = {
+            let mut t = Vec::new();
+            t.extend_from_slice(&0u16.to_be_bytes()); // version
+            t.extend_from_slice(&1u16.to_be_bytes()); // numTables
+            t.extend_from_slice(&3u16.to_be_bytes()); // platformID (Windows)
+            t.extend_from_slice(&1u16.to_be_bytes()); // encodingID (BMP)
+            t.extend_from_slice(&12u32.to_be_bytes()); // offset to subtable
+            t.extend_from_slice(&cmap_subtable);
+            t
+        };
+
+        // post format 2.0: gid0=".notdef" (standard Macintosh index 0),
+        // gid1="A" and gid2="A.sc" as custom Pascal strings.
+        let post = {
+            let mut t = Vec::new();
+            t.extend_from_slice(&0x00020000u32.to_be_bytes()); // version
+            t.extend_from_slice(&0i32.to_be_bytes()); // italicAngle
+            t.extend_from_slice(&0i16.to_be_bytes()); // underlinePosition
+            t.extend_from_slice(&0i16.to_be_bytes()); // underlineThickness
+            t.extend_from_slice(&0u32.to_be_bytes()); // isFixedPitch
+            for _ in 0..4 {
+                t.extend_from_slice(&0u32.to_be_bytes()); // min/maxMemType42/1
+            }
+            t.extend_from_slice(&3u16.to_be_bytes()); // numGlyphs
+            t.extend_from_slice(&0u16.to_be_bytes()); // glyphNameIndex[0] = .notdef
+            t.extend_from_slice(&258u16.to_be_bytes()); // glyphNameIndex[1] = "A"
+            t.extend_from_slice(&259u16.to_be_bytes()); // glyphNameIndex[2] = "A.sc"
+            t.push(1);
+            t.extend_from_slice(b"A");
+            t.push(4);
+            t.extend_from_slice(b"A.sc");
+            t
+        };
+
+        let tables: Vec<(&[u8; 4], Vec<u8>)> = vec![
+            (b"cmap", pad4(cmap)),
+            (b"glyf", pad4(glyf)),
+            (b"head", pad4(head)),
+            (b"hhea", pad4(hhea)),
+            (b"hmtx", pad4(hmtx)),
+            (b"loca", pad4(loca)),
+            (b"maxp", pad4(maxp)),
+            (b"post", pad4(post)),
+        ];
+
+        let num_tables = tables.len() as u16;
+        let header_len = 12 + 16 * tables.len();
+        let mut out = Vec::new();
+        out.extend_from_slice(&0x00010000u32.to_be_bytes());
+        out.extend_from_slice(&num_tables.to_be_bytes());
+        out.extend_from_slice(&0u16.to_be_bytes()); // searchRange
+        out.extend_from_slice(&0u16.to_be_bytes()); // entrySelector
+        out.extend_from_slice(&0u16.to_be_bytes()); // rangeShift
+
+        let mut offset = header_len;
+        let mut directory = Vec::new();
+        let mut data = Vec::new();
+        for (tag, bytes) in &tables {
+            directory.extend_from_slice(*tag);
+            directory.extend_from_slice(&0u32.to_be_bytes()); // checksum (unchecked by readers here)
+            directory.extend_from_slice(&(offset as u32).to_be_bytes());
+            directory.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+            offset += bytes.len();
+            data.extend_from_slice(bytes);
+        }
+        out.extend_from_slice(&directory);
+        out.extend_from_slice(&data);
+        out
+    }
+
+    #[test]
+    fn resolve_glyph_by_codepoint() {
+        let sfnt = build_test_font();
+        let font = SkrifaFontRef::new(&sfnt).unwrap();
+        assert_eq!(resolve_glyph(&font, "A").unwrap(), GlyphId::new(1));
+    }
+
+    #[test]
+    fn resolve_glyph_by_multi_char_name_does_not_fall_back_to_first_char_codepoint() {
+        let sfnt = build_test_font();
+        let font = SkrifaFontRef::new(&sfnt).unwrap();
+        // Regression test: "A.sc" starts with 'A', which IS in cmap. A
+        // correct lookup must resolve the *name* "A.sc" (gid 2) rather
+        // than silently returning the "A" glyph (gid 1).
+        assert_eq!(resolve_glyph(&font, "A.sc").unwrap(), GlyphId::new(2));
+    }
+
+    #[test]
+    fn resolve_glyph_unknown_name_errors() {
+        let sfnt = build_test_font();
+        let font = SkrifaFontRef::new(&sfnt).unwrap();
+        assert!(resolve_glyph(&font, "nonexistent").is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Trim a `babelfont::Font` down to a requested Unicode set before handing
+//! it to `BabelfontIrSource`, so the web editor can ship tiny per-page
+//! subsets for preview without a separate `hb-subset` pass.
+
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+/// A requested codepoint set: either single codepoints or `[start, end]`
+/// inclusive ranges, as produced by `Intl.Segmenter`-style page scans on
+/// the JS side.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum CodepointSpec {
+    Single(u32),
+    Range([u32; 2]),
+}
+
+/// The highest valid Unicode scalar value. Ranges are validated against
+/// this before being materialized, so a bogus or adversarial `[start, end]`
+/// can't blow up the subset request into billions of entries.
+const MAX_CODEPOINT: u32 = 0x10FFFF;
+
+/// Parse the requested codepoint list/ranges into a flat, deduplicated set.
+pub fn parse_codepoints(codepoints_json: &str) -> Result<BTreeSet<u32>, String> {
+    let specs: Vec<CodepointSpec> = serde_json::from_str(codepoints_json)
+        .map_err(|e| format!("Invalid codepoints JSON: {e}"))?;
+    let mut set = BTreeSet::new();
+    for spec in specs {
+        match spec {
+            CodepointSpec::Single(cp) => {
+                if cp > MAX_CODEPOINT {
+                    return Err(format!("Codepoint {cp:#x} exceeds U+10FFFF"));
+                }
+                set.insert(cp);
+            }
+            CodepointSpec::Range([start, end]) => {
+                if start > end {
+                    return Err(format!("Invalid range [{start}, {end}]: start > end"));
+                }
+                if end > MAX_CODEPOINT {
+                    return Err(format!("Range end {end:#x} exceeds U+10FFFF"));
+                }
+                set.extend(start..=end);
+            }
+        }
+    }
+    Ok(set)
+}
+
+/// Drop every glyph not reachable from `codepoints`, keeping `.notdef` and
+/// transitively pulling in component glyphs referenced by composites, then
+/// prune cmap entries and GSUB/GPOS rules that reference removed glyphs.
+pub fn subset(font: &mut babelfont::Font, codepoints: &BTreeSet<u32>) -> Result<(), String> {
+    let mut keep: BTreeSet<String> = BTreeSet::new();
+    keep.insert(".notdef".to_string());
+
+    for &cp in codepoints {
+        if let Some(glyph) = font.glyph_for_codepoint(cp) {
+            keep.insert(glyph.name.clone());
+        }
+    }
+
+    // Transitively pull in component glyphs so composites don't break.
+    let mut frontier: Vec<String> = keep.iter().cloned().collect();
+    while let Some(name) = frontier.pop() {
+        let Some(glyph) = font.glyphs.iter().find(|g| g.name == name) else {
+            continue;
+        };
+        for component_name in glyph.component_names() {
+            if keep.insert(component_name.clone()) {
+                frontier.push(component_name);
+            }
+        }
+    }
+
+    font.glyphs.retain(|g| keep.contains(&g.name));
+
+    for glyph in font.glyphs.iter_mut() {
+        glyph.codepoints.retain(|cp| codepoints.contains(cp));
+    }
+
+    prune_layout_rules(font, &keep);
+
+    Ok(())
+}
+
+/// Remove GSUB/GPOS rules (and feature/lookup references) that mention a
+/// glyph no longer present in the font.
+fn prune_layout_rules(font: &mut babelfont::Font, keep: &BTreeSet<String>) {
+    for feature in font.features.iter_mut() {
+        feature.retain_rules_referencing_only(keep);
+    }
+}